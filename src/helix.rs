@@ -0,0 +1,190 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+const TOKEN_URL: &str = "https://id.twitch.tv/oauth2/token";
+const HELIX_URL: &str = "https://api.twitch.tv/helix";
+
+/// Minimal client-credentials OAuth client for the Twitch Helix API.
+///
+/// Holds on to the app access token once fetched and reuses it for
+/// subsequent calls; callers don't need to know whether a request triggered
+/// a fresh token fetch or not.
+pub struct HelixClient {
+    client_id: String,
+    client_secret: String,
+    http: reqwest::Client,
+    token: tokio::sync::Mutex<Option<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamsResponse {
+    data: Vec<StreamData>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct StreamData {
+    pub id: String,
+    pub title: String,
+    pub game_name: String,
+    pub started_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsersResponse {
+    data: Vec<UserData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserData {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScheduleResponse {
+    data: ScheduleData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScheduleData {
+    segments: Vec<ScheduleSegment>,
+}
+
+/// A single upcoming broadcast on a channel's published schedule.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ScheduleSegment {
+    pub id: String,
+    pub title: String,
+    pub start_time: String,
+}
+
+impl ScheduleSegment {
+    /// Seconds from now until `start_time`, or `None` if it has already
+    /// passed or the timestamp couldn't be parsed.
+    pub fn seconds_until_start(&self) -> Option<f64> {
+        let start = chrono::DateTime::parse_from_rfc3339(&self.start_time).ok()?;
+        let secs = (start.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_milliseconds() as f64 / 1000.0;
+        (secs > 0.0).then_some(secs)
+    }
+}
+
+impl HelixClient {
+    pub fn new(client_id: String, client_secret: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            http: reqwest::Client::new(),
+            token: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    async fn app_access_token(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+        if let Some(existing) = token.as_ref() {
+            return Ok(existing.clone());
+        }
+
+        let response: TokenResponse = self
+            .http
+            .post(TOKEN_URL)
+            .query(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("grant_type", "client_credentials"),
+            ])
+            .send()
+            .await
+            .context("Failed to request Twitch app access token")?
+            .error_for_status()
+            .context("Twitch token endpoint returned an error")?
+            .json()
+            .await
+            .context("Failed to parse Twitch token response")?;
+
+        *token = Some(response.access_token.clone());
+        Ok(response.access_token)
+    }
+
+    /// Looks up the currently live stream for `login`, if any.
+    pub async fn get_stream(&self, login: &str) -> Result<Option<StreamData>> {
+        let token = self.app_access_token().await?;
+
+        let response = self
+            .http
+            .get(format!("{}/streams", HELIX_URL))
+            .header("Client-Id", &self.client_id)
+            .bearer_auth(&token)
+            .query(&[("user_login", login)])
+            .send()
+            .await
+            .context("Failed to query Twitch Get Streams endpoint")?
+            .error_for_status()
+            .context("Twitch Get Streams endpoint returned an error")?
+            .json::<StreamsResponse>()
+            .await
+            .context("Failed to parse Twitch Get Streams response")?;
+
+        Ok(response.data.into_iter().next())
+    }
+
+    async fn user_id(&self, login: &str) -> Result<String> {
+        let token = self.app_access_token().await?;
+
+        let response: UsersResponse = self
+            .http
+            .get(format!("{}/users", HELIX_URL))
+            .header("Client-Id", &self.client_id)
+            .bearer_auth(&token)
+            .query(&[("login", login)])
+            .send()
+            .await
+            .context("Failed to query Twitch Get Users endpoint")?
+            .error_for_status()
+            .context("Twitch Get Users endpoint returned an error")?
+            .json()
+            .await
+            .context("Failed to parse Twitch Get Users response")?;
+
+        response
+            .data
+            .into_iter()
+            .next()
+            .map(|user| user.id)
+            .with_context(|| format!("No Twitch user found for login {}", login))
+    }
+
+    /// Looks up the next upcoming segment on `login`'s published schedule, if
+    /// any. Returns `None` both when the channel has no schedule configured
+    /// and when it has one but no upcoming segments.
+    pub async fn get_next_segment(&self, login: &str) -> Result<Option<ScheduleSegment>> {
+        let token = self.app_access_token().await?;
+        let broadcaster_id = self.user_id(login).await?;
+
+        let response = self
+            .http
+            .get(format!("{}/schedule", HELIX_URL))
+            .header("Client-Id", &self.client_id)
+            .bearer_auth(&token)
+            .query(&[("broadcaster_id", broadcaster_id.as_str())])
+            .send()
+            .await
+            .context("Failed to query Twitch Get Channel Stream Schedule endpoint")?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let response: ScheduleResponse = response
+            .error_for_status()
+            .context("Twitch Get Channel Stream Schedule endpoint returned an error")?
+            .json()
+            .await
+            .context("Failed to parse Twitch Get Channel Stream Schedule response")?;
+
+        Ok(response.data.segments.into_iter().next())
+    }
+}
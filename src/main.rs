@@ -1,18 +1,31 @@
+mod helix;
+mod notifier;
+mod splits;
+mod tools;
+mod watchlist;
+mod webserver;
+
 use anyhow::{Context, Result};
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
 use clap::Parser;
 use directories::ProjectDirs;
+use helix::{HelixClient, ScheduleSegment, StreamData};
+use notifier::{build_notifier, Event as NotifierEvent, Notifier, NotifierConfig};
 use serde::{Deserialize, Serialize};
+use splits::{splits_for_attempt, Split};
+use std::collections::HashMap;
 use std::fs;
 use std::io::{self, Write};
 use std::path::PathBuf;
-use std::process::Command;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::{sleep, Duration};
+use tools::{build_converter, build_downloader, Converter, Downloader, ToolConfig};
 use url::Url;
 use vcsr::{args, process_file as vcsr_process_file};
 use walkdir::WalkDir;
+use watchlist::{load_watchlist, ChannelConfig};
+use webserver::WebServerConfig;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -28,32 +41,123 @@ struct Args {
     /// Twitch VOD or Clip URL
     #[arg(short, long)]
     video_url: Option<String>,
+
+    /// Watch every channel listed in the watchlist file instead of a single username
+    #[arg(short, long)]
+    watchlist: bool,
+
+    /// Run the embedded HTTP control server and process jobs submitted to it
+    #[arg(long)]
+    serve: bool,
+
+    /// Wait for a channel's next scheduled broadcast instead of only polling every check_interval
+    #[arg(long)]
+    wait_for_schedule: bool,
+
+    /// Path to a LiveSplit .lss splits file; cuts a highlight clip per split from `--vod-path`
+    #[arg(long)]
+    splits_file: Option<String>,
+
+    /// Path to the downloaded VOD to cut highlight clips from (used with --splits-file)
+    #[arg(long)]
+    vod_path: Option<String>,
+
+    /// LiveSplit attempt id to read split times for (used with --splits-file)
+    #[arg(long)]
+    attempt_id: Option<String>,
+
+    /// Seconds into the VOD where the run's timer started (used with --splits-file)
+    #[arg(long, default_value_t = 0.0)]
+    anchor_offset: f64,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct Settings {
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub(crate) struct Settings {
     output_folder: String,
     convert_to_mp4: bool,
     use_ffmpeg_convert: bool,
     generate_contact_sheet: bool,
     check_interval: u64,
+    wait_for_schedule: bool,
+    max_wait: f64,
+    client_id: String,
+    client_secret: String,
+    watchlist_path: String,
+    pub(crate) downloader: String,
+    quality: String,
+    tags: Vec<String>,
+    pub(crate) streamlink: ToolConfig,
+    pub(crate) yt_dlp: ToolConfig,
+    pub(crate) ffmpeg: ToolConfig,
+    pad_start: f64,
+    pad_end: f64,
+    pub(crate) notifier: NotifierConfig,
+    pub(crate) webserver: WebServerConfig,
+}
+
+/// Metadata captured from Helix at the moment a recording starts, written
+/// out as a `.json` sidecar next to the recorded file.
+#[derive(Debug, Serialize)]
+struct StreamMetadata<'a> {
+    username: &'a str,
+    stream_id: &'a str,
+    title: &'a str,
+    game_name: &'a str,
+    started_at: &'a str,
+    tags: &'a [String],
 }
 
-struct RecordingState {
+pub(crate) struct RecordingState {
     current_file: Option<PathBuf>,
 }
 
+/// All in-progress recordings/downloads, keyed by channel username (or by
+/// the source URL for one-shot VOD/clip downloads), so `handle_interrupt` and
+/// `cleanup` can finalize every file currently being written, not just one.
+pub(crate) type SharedStates = Arc<Mutex<HashMap<String, Arc<Mutex<RecordingState>>>>>;
+
+/// Registers a fresh `RecordingState` for `key` in the shared map and
+/// returns the per-key handle callers should pass around from then on.
+pub(crate) async fn register_state(states: &SharedStates, key: &str) -> Arc<Mutex<RecordingState>> {
+    let state = Arc::new(Mutex::new(RecordingState { current_file: None }));
+    states
+        .lock()
+        .await
+        .insert(key.to_string(), Arc::clone(&state));
+    state
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
-    let config = load_config()?;
+    let mut config = load_config()?;
+    config.wait_for_schedule |= args.wait_for_schedule;
+
+    if let Some(splits_file) = args.splits_file {
+        let vod_path = args.vod_path.context("--vod-path is required with --splits-file")?;
+        let attempt_id = args
+            .attempt_id
+            .context("--attempt-id is required with --splits-file")?;
+        return process_splits(
+            &PathBuf::from(splits_file),
+            &PathBuf::from(vod_path),
+            &attempt_id,
+            args.anchor_offset,
+            &config,
+        )
+        .await;
+    }
 
     let output_dir = args.output_dir.unwrap_or(config.output_folder.clone());
+    let states: SharedStates = Arc::new(Mutex::new(HashMap::new()));
+    let downloader = build_downloader(&config.downloader, config.streamlink.clone(), config.yt_dlp.clone());
+    let converter = build_converter(config.ffmpeg.clone());
+    let notifier = build_notifier(&config.notifier);
 
     if let Some(video_url) = args.video_url {
         let url = Url::parse(&video_url).context("Invalid URL")?;
-        let state = Arc::new(Mutex::new(RecordingState { current_file: None }));
-        let state_clone = Arc::clone(&state);
+        let state = register_state(&states, &video_url).await;
+        let states_clone = Arc::clone(&states);
 
         tokio::select! {
             result = async {
@@ -61,26 +165,60 @@ async fn main() -> Result<()> {
                     Some("www.twitch.tv") | Some("twitch.tv") => {
                         let path_segments: Vec<&str> = url.path_segments().unwrap().collect();
                         match path_segments.get(0) {
-                            Some(&"videos") => process_vod(&video_url, &output_dir, &config, &state).await?,
+                            Some(&"videos") => process_vod(&video_url, &output_dir, &config, &state, &*downloader, &*converter, &*notifier, None).await?,
                             Some(&"clip") | Some(_) if path_segments.contains(&"clip") => {
-                                process_clip(&video_url, &output_dir, &config, &state).await?
+                                process_clip(&video_url, &output_dir, &config, &state, &*downloader, &*converter, &*notifier, None).await?
                             }
                             _ => return Err(anyhow::anyhow!("Invalid Twitch URL")),
                         }
                     }
-                    Some("clips.twitch.tv") => process_clip(&video_url, &output_dir, &config, &state).await?,
+                    Some("clips.twitch.tv") => process_clip(&video_url, &output_dir, &config, &state, &*downloader, &*converter, &*notifier, None).await?,
                     _ => return Err(anyhow::anyhow!("Invalid Twitch URL")),
                 }
                 Ok(())
             } => {
                 if let Err(e) = result {
                     eprintln!("Processing error: {}", e);
+                    let _ = notifier.notify(NotifierEvent::Error { message: &e.to_string() }).await;
+                }
+            }
+            _ = handle_interrupt(states_clone) => {}
+        }
+
+        cleanup(&config, &states, &*converter).await?;
+    } else if args.serve {
+        if !config.webserver.enabled {
+            return Err(anyhow::anyhow!(
+                "--serve was passed but [webserver].enabled is false in the config"
+            ));
+        }
+
+        let queue = webserver::JobQueue::new();
+        tokio::select! {
+            result = webserver::serve(&config.webserver, queue.clone()) => {
+                if let Err(e) = result {
+                    eprintln!("Webserver error: {}", e);
                 }
             }
-            _ = handle_interrupt(state_clone) => {}
+            _ = webserver::run_worker(queue, config.clone(), output_dir.clone()) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("Received Ctrl+C, shutting down gracefully ᗜˬᗜ ");
+            }
         }
+    } else if args.watchlist {
+        let states_clone = Arc::clone(&states);
 
-        cleanup(&config, &state).await?;
+        tokio::select! {
+            result = run_watchlist(&config, &output_dir, &states) => {
+                if let Err(e) = result {
+                    eprintln!("Watchlist error: {}", e);
+                    let _ = notifier.notify(NotifierEvent::Error { message: &e.to_string() }).await;
+                }
+            }
+            _ = handle_interrupt(states_clone) => {}
+        }
+
+        cleanup(&config, &states, &*converter).await?;
     } else {
         let twitch_username = args.username.unwrap_or_else(|| {
             println!("Streamer Username to record:");
@@ -89,24 +227,95 @@ async fn main() -> Result<()> {
             input.trim().to_string()
         });
 
-        let state = Arc::new(Mutex::new(RecordingState { current_file: None }));
-        let state_clone = Arc::clone(&state);
+        let state = register_state(&states, &twitch_username).await;
+        let states_clone = Arc::clone(&states);
 
         tokio::select! {
-            result = record_stream(&twitch_username, &config, &state, &output_dir) => {
+            result = record_stream(&twitch_username, &config, &state, &output_dir, &*downloader, &*converter, &*notifier) => {
                 if let Err(e) = result {
                     eprintln!("Recording error: {}", e);
+                    let _ = notifier.notify(NotifierEvent::Error { message: &e.to_string() }).await;
                 }
             }
-            _ = handle_interrupt(state_clone) => {}
+            _ = handle_interrupt(states_clone) => {}
         }
 
-        cleanup(&config, &state).await?;
+        cleanup(&config, &states, &*converter).await?;
+    }
+
+    Ok(())
+}
+
+/// Spawns one task per channel in the watchlist, each independently polling,
+/// recording, and processing its own files while sharing `states` so an
+/// interrupt can finalize every channel's in-progress recording.
+async fn run_watchlist(config: &Settings, output_dir: &str, states: &SharedStates) -> Result<()> {
+    let watchlist = load_watchlist(&config.watchlist_path)?;
+    if watchlist.channels.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Watchlist at {} has no channels configured",
+            config.watchlist_path
+        ));
+    }
+
+    let mut tasks = Vec::with_capacity(watchlist.channels.len());
+    for channel in watchlist.channels {
+        let config = channel_config(config, &channel);
+        let channel_output_dir = match &channel.output_subfolder {
+            Some(subfolder) => PathBuf::from(output_dir)
+                .join(subfolder)
+                .to_string_lossy()
+                .into_owned(),
+            None => output_dir.to_string(),
+        };
+        let username = channel.username.clone();
+        let state = register_state(states, &username).await;
+
+        tasks.push(tokio::spawn(async move {
+            let downloader = build_downloader(&config.downloader, config.streamlink.clone(), config.yt_dlp.clone());
+            let converter = build_converter(config.ffmpeg.clone());
+            let notifier = build_notifier(&config.notifier);
+            if let Err(e) = record_stream(
+                &username,
+                &config,
+                &state,
+                &channel_output_dir,
+                &*downloader,
+                &*converter,
+                &*notifier,
+            )
+            .await
+            {
+                eprintln!("Recording error for {}: {}", username, e);
+                let _ = notifier
+                    .notify(NotifierEvent::Error { message: &e.to_string() })
+                    .await;
+            }
+        }));
+    }
+
+    for task in tasks {
+        task.await.context("Watchlist channel task panicked")?;
     }
 
     Ok(())
 }
 
+/// Layers a channel's per-channel overrides on top of the global settings.
+fn channel_config(config: &Settings, channel: &ChannelConfig) -> Settings {
+    let mut config = config.clone();
+    if let Some(generate_contact_sheet) = channel.generate_contact_sheet {
+        config.generate_contact_sheet = generate_contact_sheet;
+    }
+    if let Some(quality) = &channel.quality {
+        config.quality = quality.clone();
+    }
+    if !channel.tags.is_empty() {
+        config.tags = channel.tags.clone();
+    }
+    config
+}
+
 fn load_config() -> Result<Settings> {
     let project_dirs = ProjectDirs::from("", "", "twitch-scrapurr")
         .context("Failed to get project directories")?;
@@ -123,6 +332,41 @@ convert_to_mp4 = true
 generate_contact_sheet = true
 use_ffmpeg_convert = true
 check_interval = 60
+wait_for_schedule = false
+max_wait = 21600.0
+client_id = ""
+client_secret = ""
+watchlist_path = "./watchlist.yaml"
+downloader = "streamlink"
+quality = "best"
+tags = []
+pad_start = 10.0
+pad_end = 10.0
+
+[streamlink]
+executable_path = "streamlink"
+working_directory = ""
+args = ["--twitch-disable-ads"]
+
+[yt_dlp]
+executable_path = "yt-dlp"
+working_directory = ""
+args = []
+
+[ffmpeg]
+executable_path = "ffmpeg"
+working_directory = ""
+args = []
+
+[notifier]
+type = "none"
+webhook_url = ""
+bot_token = ""
+chat_id = ""
+
+[webserver]
+enabled = false
+bind_address = "127.0.0.1:8787"
 "#;
         fs::write(&config_path, default_config)?;
         default_config.to_string()
@@ -145,61 +389,146 @@ check_interval = 60
     Ok(settings)
 }
 
-async fn handle_interrupt(state: Arc<Mutex<RecordingState>>) {
+async fn handle_interrupt(states: SharedStates) {
     tokio::signal::ctrl_c()
         .await
         .expect("Failed to listen for ctrl+c");
     println!("Received Ctrl+C, shutting down gracefully ᗜˬᗜ ");
-    let state = state.lock().await;
-    if let Some(current_file) = &state.current_file {
-        println!("Interrupt received. Current file: {:?}", current_file);
+    for (key, state) in states.lock().await.iter() {
+        let state = state.lock().await;
+        if let Some(current_file) = &state.current_file {
+            println!("Interrupt received for {}. Current file: {:?}", key, current_file);
+        }
     }
 }
 
-async fn record_stream(
+pub(crate) async fn record_stream(
+    username: &str,
+    config: &Settings,
+    state: &Arc<Mutex<RecordingState>>,
+    output_dir: &str,
+    downloader: &dyn Downloader,
+    converter: &dyn Converter,
+    notifier: &dyn Notifier,
+) -> Result<()> {
+    record_stream_with_progress(username, config, state, output_dir, downloader, converter, notifier, None).await
+}
+
+/// How often to re-poll once a scheduled broadcast's start time is within
+/// `check_interval` seconds, instead of busy-looping on every scheduler tick.
+const TIGHT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Same as `record_stream`, but reports download progress to `on_progress`
+/// (used by the webserver job worker to surface live progress stats).
+pub(crate) async fn record_stream_with_progress(
     username: &str,
     config: &Settings,
     state: &Arc<Mutex<RecordingState>>,
     output_dir: &str,
+    downloader: &dyn Downloader,
+    converter: &dyn Converter,
+    notifier: &dyn Notifier,
+    mut on_progress: tools::ProgressSink<'_>,
 ) -> Result<()> {
     let user_vod_folder = PathBuf::from(&output_dir).join(username).join("vods");
     std::fs::create_dir_all(&user_vod_folder)?;
 
+    let helix = HelixClient::new(config.client_id.clone(), config.client_secret.clone());
+    // Segment already fetched for the upcoming broadcast we're tight-polling
+    // towards, so repeated ticks within the lead-in window only re-check live
+    // status instead of re-hitting the user_id + schedule endpoints.
+    let mut cached_segment: Option<ScheduleSegment> = None;
+
     loop {
-        let stream_url = format!("https://www.twitch.tv/{}", username);
-        let output = Command::new("streamlink")
-            .args(&["--stream-url", &stream_url, "best"])
-            .output()?;
+        let stream = helix
+            .get_stream(username)
+            .await
+            .context("Failed to query Twitch for live status")?;
 
-        if output.status.success() {
+        if let Some(stream) = stream {
             println!("Stream is live! Recording {}'s stream.", username);
-            let timestamp = Local::now().format("%d_%m_%y-%H_%M").to_string();
-            let ts_filename = format!("{}-{}.ts", username, timestamp);
-            let ts_filepath = user_vod_folder.join(&ts_filename);
+            let _ = notifier
+                .notify(NotifierEvent::Live { username, title: &stream.title })
+                .await;
+            let stream_url = format!("https://www.twitch.tv/{}", username);
+            let ts_filepath = recording_filepath(&user_vod_folder, username, &stream);
 
             {
                 let mut state = state.lock().await;
                 state.current_file = Some(ts_filepath.clone());
             }
 
-            let streamlink_status = Command::new("streamlink")
-                .args(&[
-                    "--twitch-disable-ads",
-                    &stream_url,
-                    "best",
-                    "-o",
-                    ts_filepath.to_str().unwrap(),
-                ])
-                .status()?;
-
-            if streamlink_status.success() {
-                println!("Stream ended. Processing file...");
-                process_file(&config, &ts_filepath).await?;
+            if let Err(e) = write_stream_metadata(&ts_filepath, username, &stream, &config.tags) {
+                println!("Failed to write stream metadata sidecar: {}", e);
             }
 
+            let ts_filepath_str = ts_filepath.to_string_lossy().into_owned();
+            let _ = notifier
+                .notify(NotifierEvent::RecordingStarted { username, file_path: &ts_filepath_str })
+                .await;
+
+            let downloaded = downloader.download(
+                &stream_url,
+                &ts_filepath,
+                &config.quality,
+                &[],
+                on_progress.as_deref_mut(),
+            )?;
+
+            if downloaded {
+                println!("Stream ended. Processing file...");
+                let duration_secs = DateTime::parse_from_rfc3339(&stream.started_at)
+                    .ok()
+                    .map(|started| (Utc::now() - started.with_timezone(&Utc)).num_milliseconds() as f64 / 1000.0);
+                let _ = notifier
+                    .notify(NotifierEvent::RecordingFinished {
+                        file_path: &ts_filepath_str,
+                        title: &stream.title,
+                        duration_secs,
+                    })
+                    .await;
+                process_file(&config, &ts_filepath, converter, notifier, on_progress.as_deref_mut()).await?;
+            }
+            cached_segment = None;
             println!("Waiting briefly before checking for the next stream...");
         } else {
             println!("No available streams found for {}.", username);
+
+            if config.wait_for_schedule {
+                let segment = match cached_segment.take() {
+                    Some(segment) => Some(segment),
+                    None => match helix.get_next_segment(username).await {
+                        Ok(segment) => segment,
+                        Err(e) => {
+                            println!("Failed to fetch schedule for {}: {}", username, e);
+                            None
+                        }
+                    },
+                };
+
+                if let Some(segment) = segment {
+                    if let Some(until_start) = segment.seconds_until_start() {
+                        let wait_secs = until_start.min(config.max_wait);
+                        let lead_in = (config.check_interval as f64).min(wait_secs);
+                        if wait_secs > lead_in {
+                            println!(
+                                "{}'s next scheduled broadcast is \"{}\" in {:.0}s. Sleeping until shortly before it starts...",
+                                username, segment.title, until_start
+                            );
+                            sleep(Duration::from_secs_f64(wait_secs - lead_in)).await;
+                        } else {
+                            // Inside the lead-in window: keep the segment we
+                            // already fetched cached, so every tight-poll tick
+                            // only re-checks live status instead of re-hitting
+                            // the user_id + schedule endpoints.
+                            cached_segment = Some(segment);
+                            sleep(TIGHT_POLL_INTERVAL).await;
+                        }
+                        continue;
+                    }
+                }
+            }
+
             println!(
                 "Checking for {} stream again in {} seconds...",
                 username, config.check_interval
@@ -210,7 +539,46 @@ async fn record_stream(
     }
 }
 
-async fn process_file(config: &Settings, ts_filepath: &PathBuf) -> Result<()> {
+/// Builds the `{username}-{game}-{started_at}.ts` path for a newly detected
+/// live stream, sanitizing the game name so it is safe to use in a filename.
+fn recording_filepath(user_vod_folder: &PathBuf, username: &str, stream: &StreamData) -> PathBuf {
+    let sanitized_game: String = stream
+        .game_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let sanitized_started_at: String = stream
+        .started_at
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+
+    let ts_filename = format!("{}-{}-{}.ts", username, sanitized_game, sanitized_started_at);
+    user_vod_folder.join(&ts_filename)
+}
+
+/// Writes the `.json` sidecar carrying the Helix metadata for a recording.
+fn write_stream_metadata(ts_filepath: &PathBuf, username: &str, stream: &StreamData, tags: &[String]) -> Result<()> {
+    let metadata = StreamMetadata {
+        username,
+        stream_id: &stream.id,
+        title: &stream.title,
+        game_name: &stream.game_name,
+        started_at: &stream.started_at,
+        tags,
+    };
+    let metadata_path = ts_filepath.with_extension("json");
+    fs::write(metadata_path, serde_json::to_string_pretty(&metadata)?)?;
+    Ok(())
+}
+
+async fn process_file(
+    config: &Settings,
+    ts_filepath: &PathBuf,
+    converter: &dyn Converter,
+    notifier: &dyn Notifier,
+    on_progress: tools::ProgressSink<'_>,
+) -> Result<()> {
     if !ts_filepath.exists() || ts_filepath.metadata()?.len() == 0 {
         println!("File is empty or does not exist. Skipping processing.");
         return Ok(());
@@ -219,20 +587,16 @@ async fn process_file(config: &Settings, ts_filepath: &PathBuf) -> Result<()> {
     let mp4_filepath = if config.convert_to_mp4 {
         let mp4_filepath = ts_filepath.with_extension("mp4");
         if config.use_ffmpeg_convert {
-            let ffmpeg_status = Command::new("ffmpeg")
-                .args(&[
-                    "-i",
-                    ts_filepath.to_str().unwrap(),
-                    "-c",
-                    "copy",
-                    "-y", // Overwrite output file if it exists
-                    mp4_filepath.to_str().unwrap(),
-                ])
-                .status()?;
-
-            if ffmpeg_status.success() {
+            let converted = converter.convert(ts_filepath, &mp4_filepath, on_progress)?;
+
+            if converted {
                 std::fs::remove_file(ts_filepath)?;
                 println!("[ffmpeg] Converted and saved as: {:?}", mp4_filepath);
+                let _ = notifier
+                    .notify(NotifierEvent::ConversionDone {
+                        file_path: &mp4_filepath.to_string_lossy(),
+                    })
+                    .await;
                 mp4_filepath
             } else {
                 println!("[ffmpeg] Conversion failed. Keeping original file.");
@@ -249,19 +613,30 @@ async fn process_file(config: &Settings, ts_filepath: &PathBuf) -> Result<()> {
     };
 
     if config.generate_contact_sheet {
-        if let Err(e) = generate_contact_sheet(&mp4_filepath).await {
-            println!("Failed to generate contact sheet: {}", e);
+        match generate_contact_sheet(&mp4_filepath).await {
+            Ok(contact_sheet) => {
+                let _ = notifier
+                    .notify(NotifierEvent::ContactSheetGenerated {
+                        file_path: &contact_sheet.to_string_lossy(),
+                    })
+                    .await;
+            }
+            Err(e) => println!("Failed to generate contact sheet: {}", e),
         }
     }
 
     Ok(())
 }
 
-async fn process_vod(
+pub(crate) async fn process_vod(
     vod_url: &str,
     output_dir: &str,
     config: &Settings,
     state: &Arc<Mutex<RecordingState>>,
+    downloader: &dyn Downloader,
+    converter: &dyn Converter,
+    notifier: &dyn Notifier,
+    mut on_progress: tools::ProgressSink<'_>,
 ) -> Result<()> {
     let url = Url::parse(vod_url).context("Invalid VOD URL")?;
     let path_segments: Vec<&str> = url.path_segments().unwrap().collect();
@@ -279,25 +654,29 @@ async fn process_vod(
     }
 
     println!("Downloading VOD: {}", vod_url);
-    let mut streamlink_args = vec![
-        "--twitch-disable-ads",
-        vod_url,
-        "best",
-        "-o",
-        output_path.to_str().unwrap(),
-    ];
-
-    let timestamp_string: Option<String> = timestamp.map(|ts| ts.to_string());
-    if let Some(ts) = &timestamp_string {
-        streamlink_args.push("--twitch-start-time");
-        streamlink_args.push(ts);
+    let mut extra_args = Vec::new();
+    if let Some(ts) = timestamp {
+        if config.downloader == "streamlink" {
+            extra_args.push("--twitch-start-time".to_string());
+            extra_args.push(ts);
+        } else {
+            println!(
+                "Ignoring VOD start timestamp: --twitch-start-time is only supported by the streamlink backend"
+            );
+        }
     }
 
-    let streamlink_status = Command::new("streamlink").args(&streamlink_args).status()?;
+    let downloaded = downloader.download(
+        vod_url,
+        &output_path,
+        &config.quality,
+        &extra_args,
+        on_progress.as_deref_mut(),
+    )?;
 
-    if streamlink_status.success() {
+    if downloaded {
         println!("VOD download complete. Processing file...");
-        process_file(&config, &output_path).await?;
+        process_file(&config, &output_path, converter, notifier, on_progress.as_deref_mut()).await?;
     } else {
         println!("Failed to download VOD.");
     }
@@ -305,11 +684,15 @@ async fn process_vod(
     Ok(())
 }
 
-async fn process_clip(
+pub(crate) async fn process_clip(
     clip_url: &str,
     output_dir: &str,
     config: &Settings,
     state: &Arc<Mutex<RecordingState>>,
+    downloader: &dyn Downloader,
+    converter: &dyn Converter,
+    notifier: &dyn Notifier,
+    mut on_progress: tools::ProgressSink<'_>,
 ) -> Result<()> {
     let url = Url::parse(clip_url).context("Invalid Clip URL")?;
     let path_segments: Vec<&str> = url.path_segments().unwrap().collect();
@@ -332,19 +715,11 @@ async fn process_clip(
     }
 
     println!("Downloading Clip: {}", clip_url);
-    let streamlink_args = vec![
-        "--twitch-disable-ads",
-        clip_url,
-        "best",
-        "-o",
-        output_path.to_str().unwrap(),
-    ];
-
-    let streamlink_status = Command::new("streamlink").args(&streamlink_args).status()?;
+    let downloaded = downloader.download(clip_url, &output_path, &config.quality, &[], on_progress.as_deref_mut())?;
 
-    if streamlink_status.success() {
+    if downloaded {
         println!("Clip download complete. Processing file...");
-        process_file(&config, &output_path).await?;
+        process_file(&config, &output_path, converter, notifier, on_progress.as_deref_mut()).await?;
     } else {
         println!("Failed to download Clip.");
     }
@@ -352,15 +727,82 @@ async fn process_clip(
     Ok(())
 }
 
-async fn cleanup(config: &Settings, state: &Arc<Mutex<RecordingState>>) -> Result<()> {
-    let state = state.lock().await;
-    if let Some(current_file) = &state.current_file {
-        println!("Processing last recorded/downloaded file...");
-        process_file(config, current_file).await?;
+async fn cleanup(config: &Settings, states: &SharedStates, converter: &dyn Converter) -> Result<()> {
+    let notifier = build_notifier(&config.notifier);
+    for state in states.lock().await.values() {
+        let state = state.lock().await;
+        if let Some(current_file) = &state.current_file {
+            println!("Processing last recorded/downloaded file...");
+            process_file(config, current_file, converter, &*notifier, None).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Cuts one highlight clip per split out of `vod_path`, anchored to the
+/// VOD's real timeline via `anchor_offset`, and runs each clip through the
+/// contact sheet generator.
+async fn process_splits(
+    splits_file: &PathBuf,
+    vod_path: &PathBuf,
+    attempt_id: &str,
+    anchor_offset: f64,
+    config: &Settings,
+) -> Result<()> {
+    let splits = splits_for_attempt(splits_file, attempt_id)?;
+    let video_id = vod_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("Invalid VOD path")?;
+    let clips_folder = vod_path
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    for (n, split) in splits.iter().enumerate() {
+        let clip_path = clips_folder.join(split_clip_filename(video_id, n, split));
+        let run_time = anchor_offset + split.absolute_time_secs;
+        let start = (run_time - config.pad_start).max(0.0);
+        let end = run_time + config.pad_end;
+
+        println!(
+            "Cutting split {} ({}) at {:.2}s-{:.2}s -> {:?}",
+            n, split.segment_name, start, end, clip_path
+        );
+
+        let status = config
+            .ffmpeg
+            .command()
+            .args(&["-ss", &start.to_string(), "-to", &end.to_string(), "-i"])
+            .arg(vod_path)
+            .args(&["-c", "copy", "-y"])
+            .arg(&clip_path)
+            .status()?;
+
+        if !status.success() {
+            println!("Failed to cut split {} ({})", n, split.segment_name);
+            continue;
+        }
+
+        if let Err(e) = generate_contact_sheet(&clip_path).await {
+            println!("Failed to generate contact sheet for split {}: {}", n, e);
+        }
     }
+
     Ok(())
 }
 
+/// Builds the `{video_id}-split-{n}-{segment_name}.mp4` filename for a
+/// highlight clip, sanitizing the segment name for use in a path.
+fn split_clip_filename(video_id: &str, n: usize, split: &Split) -> String {
+    let sanitized_segment: String = split
+        .segment_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}-split-{}-{}.mp4", video_id, n, sanitized_segment)
+}
+
 async fn generate_contact_sheet(mp4_filepath: &PathBuf) -> Result<PathBuf> {
     let mut args = args::application_args();
     args.filenames = vec![mp4_filepath.to_str().unwrap().to_string()];
@@ -0,0 +1,139 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Notifier configuration, selected by `type` ("discord", "telegram", or
+/// "none") with the fields relevant to that backend filled in.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NotifierConfig {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub webhook_url: String,
+    #[serde(default)]
+    pub bot_token: String,
+    #[serde(default)]
+    pub chat_id: String,
+}
+
+/// A lifecycle event worth telling the stream owner about.
+pub enum Event<'a> {
+    Live { username: &'a str, title: &'a str },
+    RecordingStarted { username: &'a str, file_path: &'a str },
+    RecordingFinished { file_path: &'a str, title: &'a str, duration_secs: Option<f64> },
+    ConversionDone { file_path: &'a str },
+    ContactSheetGenerated { file_path: &'a str },
+    Error { message: &'a str },
+}
+
+impl Event<'_> {
+    fn message(&self) -> String {
+        match self {
+            Event::Live { username, title } => {
+                format!("🔴 {} just went live: {}", username, title)
+            }
+            Event::RecordingStarted { username, file_path } => {
+                format!("⏺️ Recording started for {}: {}", username, file_path)
+            }
+            Event::RecordingFinished { file_path, title, duration_secs } => match duration_secs {
+                Some(secs) => format!("✅ Recording finished: {} — {} ({:.0}s)", title, file_path, secs),
+                None => format!("✅ Recording finished: {} — {}", title, file_path),
+            },
+            Event::ConversionDone { file_path } => {
+                format!("🎞️ Conversion done: {}", file_path)
+            }
+            Event::ContactSheetGenerated { file_path } => {
+                format!("🖼️ Contact sheet generated: {}", file_path)
+            }
+            Event::Error { message } => format!("⚠️ Error: {}", message),
+        }
+    }
+}
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, event: Event<'_>) -> Result<()>;
+}
+
+/// Used when no notifier is configured.
+pub struct NoopNotifier;
+
+#[async_trait]
+impl Notifier for NoopNotifier {
+    async fn notify(&self, _event: Event<'_>) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub struct DiscordNotifier {
+    pub webhook_url: String,
+    http: reqwest::Client,
+}
+
+impl DiscordNotifier {
+    pub fn new(webhook_url: String) -> Self {
+        Self {
+            webhook_url,
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn notify(&self, event: Event<'_>) -> Result<()> {
+        self.http
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({ "content": event.message() }))
+            .send()
+            .await
+            .context("Failed to send Discord webhook notification")?
+            .error_for_status()
+            .context("Discord webhook returned an error")?;
+        Ok(())
+    }
+}
+
+pub struct TelegramNotifier {
+    pub bot_token: String,
+    pub chat_id: String,
+    http: reqwest::Client,
+}
+
+impl TelegramNotifier {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        Self {
+            bot_token,
+            chat_id,
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    async fn notify(&self, event: Event<'_>) -> Result<()> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        self.http
+            .post(&url)
+            .json(&serde_json::json!({ "chat_id": self.chat_id, "text": event.message() }))
+            .send()
+            .await
+            .context("Failed to send Telegram notification")?
+            .error_for_status()
+            .context("Telegram sendMessage returned an error")?;
+        Ok(())
+    }
+}
+
+/// Builds the configured `Notifier` backend.
+pub fn build_notifier(config: &NotifierConfig) -> Box<dyn Notifier> {
+    match config.kind.as_str() {
+        "discord" => Box::new(DiscordNotifier::new(config.webhook_url.clone())),
+        "telegram" => Box::new(TelegramNotifier::new(
+            config.bot_token.clone(),
+            config.chat_id.clone(),
+        )),
+        _ => Box::new(NoopNotifier),
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{bail, Context, Result};
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use std::path::Path;
+
+/// A single completed split, with its absolute offset (in seconds) from the
+/// start of the run.
+#[derive(Debug, Clone)]
+pub struct Split {
+    pub segment_name: String,
+    pub absolute_time_secs: f64,
+}
+
+/// Parses a LiveSplit `.lss` file and returns the absolute (cumulative)
+/// split times for the given attempt id, in segment order.
+///
+/// Each `Segment`'s `SegmentHistory` stores a delta time per attempt;
+/// summing those deltas in segment order gives the absolute time each
+/// split was reached, relative to the start of that attempt's run.
+pub fn splits_for_attempt(lss_path: &Path, attempt_id: &str) -> Result<Vec<Split>> {
+    let xml = std::fs::read_to_string(lss_path)
+        .with_context(|| format!("Failed to read splits file at {:?}", lss_path))?;
+
+    let mut reader = Reader::from_str(&xml);
+    reader.config_mut().trim_text(true);
+
+    let mut splits = Vec::new();
+    let mut buf = Vec::new();
+
+    let mut in_segment = false;
+    let mut in_segment_history = false;
+    let mut in_name = false;
+    let mut in_matching_history_time = false;
+    let mut in_real_time = false;
+    let mut current_segment_name: Option<String> = None;
+    let mut cumulative_secs = 0.0;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Eof => break,
+            Event::Start(e) => match e.name().as_ref() {
+                b"Segment" => {
+                    in_segment = true;
+                    current_segment_name = None;
+                }
+                b"SegmentHistory" => in_segment_history = true,
+                b"Name" if in_segment && !in_segment_history => in_name = true,
+                b"Time" if in_segment_history => {
+                    in_matching_history_time = e
+                        .attributes()
+                        .flatten()
+                        .any(|a| a.key.as_ref() == b"id" && a.value.as_ref() == attempt_id.as_bytes());
+                }
+                b"RealTime" if in_matching_history_time => in_real_time = true,
+                _ => {}
+            },
+            Event::Text(e) => {
+                let text = e.unescape()?.into_owned();
+                if in_name {
+                    current_segment_name = Some(text);
+                } else if in_real_time {
+                    cumulative_secs += parse_livesplit_time(&text)?;
+                    let name = current_segment_name
+                        .clone()
+                        .context("Segment history time found with no preceding segment name")?;
+                    splits.push(Split {
+                        segment_name: name,
+                        absolute_time_secs: cumulative_secs,
+                    });
+                }
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"Segment" => in_segment = false,
+                b"SegmentHistory" => in_segment_history = false,
+                b"Name" => in_name = false,
+                b"Time" => in_matching_history_time = false,
+                b"RealTime" => in_real_time = false,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    if splits.is_empty() {
+        bail!(
+            "No splits found for attempt id {} in {:?}",
+            attempt_id,
+            lss_path
+        );
+    }
+
+    Ok(splits)
+}
+
+/// Parses a LiveSplit time string (e.g. `"00:01:23.4560000"`) into seconds.
+fn parse_livesplit_time(time: &str) -> Result<f64> {
+    let parts: Vec<&str> = time.split(':').collect();
+    match parts.as_slice() {
+        [hours, minutes, seconds] => {
+            let hours: f64 = hours.parse().context("Invalid hours in split time")?;
+            let minutes: f64 = minutes.parse().context("Invalid minutes in split time")?;
+            let seconds: f64 = seconds.parse().context("Invalid seconds in split time")?;
+            Ok(hours * 3600.0 + minutes * 60.0 + seconds)
+        }
+        _ => bail!("Unrecognized split time format: {}", time),
+    }
+}
@@ -0,0 +1,266 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Configuration for an external tool (streamlink, yt-dlp, ffmpeg, ...):
+/// which binary to run, where to run it from, and any extra arguments the
+/// user wants merged in ahead of the required URL/output arguments.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ToolConfig {
+    pub executable_path: String,
+    pub working_directory: String,
+    pub args: Vec<String>,
+}
+
+impl ToolConfig {
+    pub(crate) fn command(&self) -> Command {
+        let mut command = Command::new(&self.executable_path);
+        if !self.working_directory.is_empty() {
+            command.current_dir(&self.working_directory);
+        }
+        command.args(&self.args);
+        command
+    }
+}
+
+/// A snapshot of an in-progress transfer, parsed from whichever tool is
+/// running it. `frame`/`time_secs`/`speed` come from ffmpeg's `frame=`/
+/// `time=`/`speed=` status lines; `percent` comes from yt-dlp's `[download]`
+/// lines; `mb_written` and `speed` come from streamlink's `Written` lines.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgressStats {
+    pub frame: Option<u64>,
+    pub time_secs: Option<f64>,
+    pub speed: Option<f64>,
+    pub percent: Option<f64>,
+    pub mb_written: Option<f64>,
+}
+
+/// Where `run_with_progress` should report stats as they're parsed. `None`
+/// means run the command normally with inherited stdio.
+pub type ProgressSink<'a> = Option<&'a mut (dyn FnMut(ProgressStats) + Send)>;
+
+/// Runs `command`, optionally piping its output through progress-line
+/// parsing and reporting each update to `on_progress`.
+///
+/// Both streams are drained concurrently on their own threads so a chatty
+/// stdout (streamlink, yt-dlp) can't fill its pipe buffer and stall the
+/// process while we're still waiting to finish reading stderr (ffmpeg), or
+/// vice versa. Lines that don't parse as progress are passed straight
+/// through to our own stdout/stderr, so nothing the child prints is lost.
+fn run_with_progress(mut command: Command, on_progress: ProgressSink) -> Result<bool> {
+    let Some(mut on_progress) = on_progress else {
+        return Ok(command.status()?.success());
+    };
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().context("Failed to capture child stdout")?;
+    let stderr = child.stderr.take().context("Failed to capture child stderr")?;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let stdout_tx = tx.clone();
+    let stdout_thread = std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(|l| l.ok()) {
+            match parse_progress_line(&line) {
+                Some(stats) => {
+                    let _ = stdout_tx.send(stats);
+                }
+                None => println!("{}", line),
+            }
+        }
+    });
+    let stderr_thread = std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(|l| l.ok()) {
+            match parse_progress_line(&line) {
+                Some(stats) => {
+                    let _ = tx.send(stats);
+                }
+                None => eprintln!("{}", line),
+            }
+        }
+    });
+
+    for stats in rx {
+        on_progress(stats);
+    }
+    let _ = stdout_thread.join();
+    let _ = stderr_thread.join();
+
+    Ok(child.wait()?.success())
+}
+
+/// Tries each tool's progress-line format in turn, since the same pipe
+/// draining is reused for streamlink, yt-dlp, and ffmpeg.
+fn parse_progress_line(line: &str) -> Option<ProgressStats> {
+    parse_ffmpeg_line(line)
+        .or_else(|| parse_ytdlp_line(line))
+        .or_else(|| parse_streamlink_line(line))
+}
+
+/// Parses a `frame=... time=HH:MM:SS.mmm speed=N.NNx` style ffmpeg status line.
+fn parse_ffmpeg_line(line: &str) -> Option<ProgressStats> {
+    let mut stats = ProgressStats::default();
+    let mut found = false;
+
+    for token in line.split_whitespace() {
+        if let Some(value) = token.strip_prefix("frame=") {
+            if let Ok(frame) = value.parse() {
+                stats.frame = Some(frame);
+                found = true;
+            }
+        } else if let Some(value) = token.strip_prefix("time=") {
+            if let Some(secs) = parse_ffmpeg_timestamp(value) {
+                stats.time_secs = Some(secs);
+                found = true;
+            }
+        } else if let Some(value) = token.strip_prefix("speed=") {
+            if let Ok(speed) = value.trim_end_matches('x').parse() {
+                stats.speed = Some(speed);
+                found = true;
+            }
+        }
+    }
+
+    found.then_some(stats)
+}
+
+/// Parses a yt-dlp `[download]  NN.N% of ... at ... ETA ...` status line.
+fn parse_ytdlp_line(line: &str) -> Option<ProgressStats> {
+    if !line.trim_start().starts_with("[download]") {
+        return None;
+    }
+    let percent = line
+        .split_whitespace()
+        .find_map(|token| token.strip_suffix('%')?.parse::<f64>().ok())?;
+    Some(ProgressStats {
+        percent: Some(percent),
+        ..Default::default()
+    })
+}
+
+/// Parses a streamlink `Written N.NN MB (N.NN MB/s)` status line.
+fn parse_streamlink_line(line: &str) -> Option<ProgressStats> {
+    let rest = line.split("Written").nth(1)?;
+    let mb_written: f64 = rest.split_whitespace().next()?.parse().ok()?;
+    let speed = rest
+        .split('(')
+        .nth(1)
+        .and_then(|s| s.split_whitespace().next())
+        .and_then(|s| s.parse::<f64>().ok());
+    Some(ProgressStats {
+        mb_written: Some(mb_written),
+        speed,
+        ..Default::default()
+    })
+}
+
+/// Parses ffmpeg's `HH:MM:SS.mmm` progress timestamp into seconds.
+fn parse_ffmpeg_timestamp(timestamp: &str) -> Option<f64> {
+    let parts: Vec<&str> = timestamp.split(':').collect();
+    match parts.as_slice() {
+        [hours, minutes, seconds] => {
+            let hours: f64 = hours.parse().ok()?;
+            let minutes: f64 = minutes.parse().ok()?;
+            let seconds: f64 = seconds.parse().ok()?;
+            Some(hours * 3600.0 + minutes * 60.0 + seconds)
+        }
+        _ => None,
+    }
+}
+
+/// Downloads a live stream or VOD/clip to a local file.
+pub trait Downloader: Send + Sync {
+    fn download(
+        &self,
+        source_url: &str,
+        output_path: &Path,
+        quality: &str,
+        extra_args: &[String],
+        on_progress: ProgressSink,
+    ) -> Result<bool>;
+}
+
+pub struct StreamlinkDownloader {
+    pub config: ToolConfig,
+}
+
+impl Downloader for StreamlinkDownloader {
+    fn download(
+        &self,
+        source_url: &str,
+        output_path: &Path,
+        quality: &str,
+        extra_args: &[String],
+        on_progress: ProgressSink,
+    ) -> Result<bool> {
+        let mut command = self.config.command();
+        command
+            .args(extra_args)
+            .args(&[source_url, quality, "-o"])
+            .arg(output_path);
+        run_with_progress(command, on_progress)
+    }
+}
+
+pub struct YtDlpDownloader {
+    pub config: ToolConfig,
+}
+
+impl Downloader for YtDlpDownloader {
+    fn download(
+        &self,
+        source_url: &str,
+        output_path: &Path,
+        quality: &str,
+        extra_args: &[String],
+        on_progress: ProgressSink,
+    ) -> Result<bool> {
+        let mut command = self.config.command();
+        command
+            .args(extra_args)
+            .args(["-f", quality])
+            .arg("-o")
+            .arg(output_path)
+            .arg(source_url);
+        run_with_progress(command, on_progress)
+    }
+}
+
+/// Converts a recorded/downloaded file from one container to another.
+pub trait Converter: Send + Sync {
+    fn convert(&self, input_path: &Path, output_path: &Path, on_progress: ProgressSink) -> Result<bool>;
+}
+
+pub struct FfmpegConverter {
+    pub config: ToolConfig,
+}
+
+impl Converter for FfmpegConverter {
+    fn convert(&self, input_path: &Path, output_path: &Path, on_progress: ProgressSink) -> Result<bool> {
+        let mut command = self.config.command();
+        command
+            .arg("-i")
+            .arg(input_path)
+            .args(&["-c", "copy", "-y"])
+            .arg(output_path);
+        run_with_progress(command, on_progress)
+    }
+}
+
+/// Builds the configured `Downloader` backend.
+pub fn build_downloader(kind: &str, streamlink: ToolConfig, yt_dlp: ToolConfig) -> Box<dyn Downloader> {
+    match kind {
+        "yt-dlp" => Box::new(YtDlpDownloader { config: yt_dlp }),
+        _ => Box::new(StreamlinkDownloader { config: streamlink }),
+    }
+}
+
+/// Builds the configured `Converter` backend.
+pub fn build_converter(ffmpeg: ToolConfig) -> Box<dyn Converter> {
+    Box::new(FfmpegConverter { config: ffmpeg })
+}
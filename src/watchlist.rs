@@ -0,0 +1,30 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// A single entry in the watchlist, with optional per-channel overrides
+/// layered on top of the global `Settings`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChannelConfig {
+    pub username: String,
+    pub output_subfolder: Option<String>,
+    pub quality: Option<String>,
+    pub generate_contact_sheet: Option<bool>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct WatchlistConfig {
+    #[serde(default)]
+    pub channels: Vec<ChannelConfig>,
+}
+
+/// Loads the watchlist YAML from `path`.
+pub fn load_watchlist(path: &str) -> Result<WatchlistConfig> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read watchlist file at {}", path))?;
+    let watchlist: WatchlistConfig = serde_yaml::from_str(&contents)
+        .with_context(|| format!("Failed to parse watchlist file at {}", path))?;
+    Ok(watchlist)
+}
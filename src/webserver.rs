@@ -0,0 +1,280 @@
+use crate::notifier::{build_notifier, Notifier};
+use crate::tools::{build_converter, build_downloader, ProgressStats};
+use crate::{process_clip, process_vod, record_stream_with_progress, register_state, Settings, SharedStates};
+use anyhow::{Context, Result};
+use axum::extract::{Path as AxumPath, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::Mutex;
+
+/// `[webserver]` config: whether the embedded control server is enabled and
+/// what address it should bind to.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WebServerConfig {
+    pub enabled: bool,
+    pub bind_address: String,
+}
+
+/// A job submitted through `POST /jobs`: either a VOD/clip URL to download,
+/// or a username to watch for the next stream.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnqueueRequest {
+    pub url: Option<String>,
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub enum JobKind {
+    Vod(String),
+    Clip(String),
+    Watch(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: String,
+    pub description: String,
+    pub status: JobStatus,
+    /// Updated synchronously from the download/convert progress callback,
+    /// a plain closure that cannot `.await`, so this is a `std::sync`
+    /// mutex rather than `tokio::sync::Mutex`.
+    pub progress: Arc<StdMutex<ProgressStats>>,
+    pub kind: JobKind,
+}
+
+/// A point-in-time snapshot of a `Job`, safe to serialize as JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobView {
+    pub id: String,
+    pub description: String,
+    pub status: JobStatus,
+    pub progress: ProgressStats,
+}
+
+impl Job {
+    fn view(&self) -> JobView {
+        JobView {
+            id: self.id.clone(),
+            description: self.description.clone(),
+            status: self.status.clone(),
+            progress: self.progress.lock().unwrap().clone(),
+        }
+    }
+}
+
+struct JobStore {
+    jobs: Mutex<HashMap<String, Job>>,
+    next_id: AtomicU64,
+}
+
+/// Shared job queue handed to every route handler and to the background
+/// worker that actually runs enqueued jobs.
+#[derive(Clone)]
+pub struct JobQueue(Arc<JobStore>);
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self(Arc::new(JobStore {
+            jobs: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }))
+    }
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn enqueue(&self, kind: JobKind, description: String) -> Job {
+        let id = format!("job-{}", self.0.next_id.fetch_add(1, Ordering::Relaxed));
+        let job = Job {
+            id: id.clone(),
+            description,
+            status: JobStatus::Queued,
+            progress: Arc::new(StdMutex::new(ProgressStats::default())),
+            kind,
+        };
+        self.0.jobs.lock().await.insert(id, job.clone());
+        job
+    }
+
+    async fn list(&self) -> Vec<JobView> {
+        self.0.jobs.lock().await.values().map(Job::view).collect()
+    }
+
+    async fn get(&self, id: &str) -> Option<JobView> {
+        self.0.jobs.lock().await.get(id).map(Job::view)
+    }
+
+    async fn next_queued(&self) -> Option<Job> {
+        let mut jobs = self.0.jobs.lock().await;
+        let job = jobs
+            .values()
+            .find(|j| j.status == JobStatus::Queued)
+            .cloned()?;
+        jobs.get_mut(&job.id).unwrap().status = JobStatus::Running;
+        Some(job)
+    }
+
+    async fn set_status(&self, id: &str, status: JobStatus) {
+        if let Some(job) = self.0.jobs.lock().await.get_mut(id) {
+            job.status = status;
+        }
+    }
+}
+
+async fn enqueue_job(
+    State(queue): State<JobQueue>,
+    Json(request): Json<EnqueueRequest>,
+) -> Result<Json<JobView>, StatusCode> {
+    if let Some(url) = request.url {
+        let kind = if url.contains("/videos/") {
+            JobKind::Vod(url.clone())
+        } else {
+            JobKind::Clip(url.clone())
+        };
+        return Ok(Json(queue.enqueue(kind, url).await.view()));
+    }
+    if let Some(username) = request.username {
+        return Ok(Json(queue.enqueue(JobKind::Watch(username.clone()), username).await.view()));
+    }
+    Err(StatusCode::BAD_REQUEST)
+}
+
+async fn list_jobs(State(queue): State<JobQueue>) -> Json<Vec<JobView>> {
+    Json(queue.list().await)
+}
+
+async fn job_progress(
+    State(queue): State<JobQueue>,
+    AxumPath(id): AxumPath<String>,
+) -> Result<Json<JobView>, StatusCode> {
+    queue.get(&id).await.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+fn router(queue: JobQueue) -> Router {
+    Router::new()
+        .route("/jobs", post(enqueue_job).get(list_jobs))
+        .route("/progress/:id", get(job_progress))
+        .with_state(queue)
+}
+
+/// Runs the embedded control server until the process is killed.
+pub async fn serve(config: &WebServerConfig, queue: JobQueue) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(&config.bind_address)
+        .await
+        .with_context(|| format!("Failed to bind webserver to {}", config.bind_address))?;
+    println!("[webserver] Listening on {}", config.bind_address);
+    axum::serve(listener, router(queue))
+        .await
+        .context("Webserver exited unexpectedly")?;
+    Ok(())
+}
+
+/// Pulls queued jobs and spawns one task per job so a long-running
+/// `Watch` job (which polls for a live stream indefinitely) can't starve
+/// every other job behind it in the queue.
+pub async fn run_worker(queue: JobQueue, config: Settings, output_dir: String) {
+    loop {
+        let Some(job) = queue.next_queued().await else {
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            continue;
+        };
+
+        println!("[webserver] Starting job {}: {}", job.id, job.description);
+        let queue = queue.clone();
+        let config = config.clone();
+        let output_dir = output_dir.clone();
+
+        tokio::spawn(async move {
+            let result = run_job(&job, &config, &output_dir).await;
+
+            match result {
+                Ok(()) => queue.set_status(&job.id, JobStatus::Completed).await,
+                Err(e) => {
+                    println!("[webserver] Job {} failed: {}", job.id, e);
+                    queue.set_status(&job.id, JobStatus::Failed).await;
+                }
+            }
+        });
+    }
+}
+
+async fn run_job(job: &Job, config: &Settings, output_dir: &str) -> Result<()> {
+    let downloader = build_downloader(&config.downloader, config.streamlink.clone(), config.yt_dlp.clone());
+    let converter = build_converter(config.ffmpeg.clone());
+    let notifier: Box<dyn Notifier> = build_notifier(&config.notifier);
+    let states: SharedStates = Arc::new(Mutex::new(HashMap::new()));
+
+    match &job.kind {
+        JobKind::Vod(url) => {
+            let state = register_state(&states, &job.id).await;
+            let progress = Arc::clone(&job.progress);
+            let mut on_progress = move |stats: ProgressStats| {
+                *progress.lock().unwrap() = stats;
+            };
+            process_vod(
+                url,
+                output_dir,
+                config,
+                &state,
+                &*downloader,
+                &*converter,
+                &*notifier,
+                Some(&mut on_progress),
+            )
+            .await
+        }
+        JobKind::Clip(url) => {
+            let state = register_state(&states, &job.id).await;
+            let progress = Arc::clone(&job.progress);
+            let mut on_progress = move |stats: ProgressStats| {
+                *progress.lock().unwrap() = stats;
+            };
+            process_clip(
+                url,
+                output_dir,
+                config,
+                &state,
+                &*downloader,
+                &*converter,
+                &*notifier,
+                Some(&mut on_progress),
+            )
+            .await
+        }
+        JobKind::Watch(username) => {
+            let state = register_state(&states, &job.id).await;
+            let progress = Arc::clone(&job.progress);
+            let mut on_progress = move |stats: ProgressStats| {
+                *progress.lock().unwrap() = stats;
+            };
+            record_stream_with_progress(
+                username,
+                config,
+                &state,
+                output_dir,
+                &*downloader,
+                &*converter,
+                &*notifier,
+                Some(&mut on_progress),
+            )
+            .await
+        }
+    }
+}